@@ -0,0 +1,223 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Instant;
+
+use clap::Args;
+use serde::{Deserialize, Serialize};
+
+use crate::importer::{ImportResult, Metrics};
+use crate::CommonArgs;
+
+#[cfg(feature = "meili")]
+use crate::meili::{MeiliConfig, MeiliImporter};
+#[cfg(feature = "surreal")]
+use crate::surreal::{SurrealConfig, SurrealImporter};
+
+#[derive(Args, Debug, Clone)]
+pub struct BenchArgs {
+    /// Path to a JSON workload file describing one or more runs
+    #[arg(long)]
+    workload: PathBuf,
+
+    /// Where to write the JSON report; stdout if omitted
+    #[arg(long)]
+    report_path: Option<PathBuf>,
+
+    /// HTTP endpoint the JSON report is POSTed to after being written
+    #[arg(long)]
+    report_url: Option<String>,
+}
+
+// One entry in a workload file: a single import run to replay, optionally
+// more than once, against a live server.
+#[derive(Debug, Clone, Deserialize)]
+struct WorkloadRun {
+    input: PathBuf,
+    backend: String,
+    target: Option<String>,
+    threads: usize,
+    batch_size: usize,
+    #[serde(default = "default_max_retries")]
+    max_retries: u32,
+    #[serde(default = "default_repeat")]
+    repeat: usize,
+
+    // SurrealDB connection fields (ignored for other backends)
+    host: Option<String>,
+    port: Option<String>,
+    user: Option<String>,
+    password: Option<String>,
+    ns: Option<String>,
+    db: Option<String>,
+
+    // Meilisearch connection fields (ignored for other backends)
+    url: Option<String>,
+    api_key: Option<String>,
+}
+
+fn default_max_retries() -> u32 {
+    5
+}
+
+fn default_repeat() -> usize {
+    1
+}
+
+#[derive(Debug, Deserialize)]
+struct Workload {
+    runs: Vec<WorkloadRun>,
+}
+
+#[derive(Debug, Serialize)]
+struct LatencyPercentiles {
+    p50_ms: f64,
+    p95_ms: f64,
+    p99_ms: f64,
+}
+
+#[derive(Debug, Serialize)]
+struct RunReport {
+    input: PathBuf,
+    backend: String,
+    threads: usize,
+    batch_size: usize,
+    repeat_index: usize,
+    duration_secs: f64,
+    documents: u64,
+    docs_per_sec: f64,
+    latency: LatencyPercentiles,
+}
+
+#[derive(Debug, Serialize)]
+struct BenchReport {
+    runs: Vec<RunReport>,
+}
+
+pub async fn run_bench(args: BenchArgs) -> ImportResult<()> {
+    let data = std::fs::read_to_string(&args.workload)?;
+    let workload: Workload = serde_json::from_str(&data)?;
+
+    let mut reports = Vec::new();
+    for run in &workload.runs {
+        for repeat_index in 0..run.repeat.max(1) {
+            println!(
+                "Bench: running {:?} against {} (repeat {}/{})",
+                run.input,
+                run.backend,
+                repeat_index + 1,
+                run.repeat.max(1)
+            );
+            reports.push(run_once(run, repeat_index).await?);
+        }
+    }
+
+    let report = BenchReport { runs: reports };
+    let rendered = serde_json::to_string_pretty(&report)?;
+
+    match &args.report_path {
+        Some(path) => std::fs::write(path, &rendered)?,
+        None => println!("{}", rendered),
+    }
+
+    if let Some(url) = &args.report_url {
+        let client = reqwest::Client::new();
+        client
+            .post(url)
+            .header("Content-Type", "application/json")
+            .body(rendered)
+            .send()
+            .await?
+            .error_for_status()?;
+    }
+
+    Ok(())
+}
+
+async fn run_once(run: &WorkloadRun, repeat_index: usize) -> ImportResult<RunReport> {
+    let common = CommonArgs {
+        input: run.input.clone(),
+        threads: run.threads,
+        batch_size: run.batch_size,
+        max_retries: run.max_retries,
+    };
+    let metrics = Arc::new(Metrics::new());
+
+    let started = Instant::now();
+    let documents = match run.backend.as_str() {
+        #[cfg(feature = "surreal")]
+        "surreal" => {
+            let config = SurrealConfig {
+                host: run.host.clone().unwrap_or_else(|| "127.0.0.1".to_string()),
+                port: run.port.clone().unwrap_or_else(|| "8000".to_string()),
+                user: run.user.clone().unwrap_or_else(|| "root".to_string()),
+                password: run.password.clone().unwrap_or_else(|| "root".to_string()),
+                ns: run.ns.clone().unwrap_or_else(|| "test".to_string()),
+                db: run.db.clone().unwrap_or_else(|| "test".to_string()),
+            };
+            crate::run::<SurrealImporter>(
+                common,
+                config,
+                run.target.clone(),
+                Some(metrics.clone()),
+                false,
+            )
+            .await?
+        }
+        #[cfg(feature = "meili")]
+        "meili" => {
+            let config = MeiliConfig {
+                url: run.url.clone().unwrap_or_default(),
+                api_key: run.api_key.clone().unwrap_or_default(),
+            };
+            crate::run::<MeiliImporter>(
+                common,
+                config,
+                run.target.clone(),
+                Some(metrics.clone()),
+                false,
+            )
+            .await?
+        }
+        other => return Err(format!("Unknown or disabled backend: {}", other).into()),
+    };
+    let duration = started.elapsed();
+
+    let latency = percentiles(&metrics.sorted_latencies());
+    let duration_secs = duration.as_secs_f64();
+    let docs_per_sec = if duration_secs > 0.0 {
+        documents as f64 / duration_secs
+    } else {
+        0.0
+    };
+
+    Ok(RunReport {
+        input: run.input.clone(),
+        backend: run.backend.clone(),
+        threads: run.threads,
+        batch_size: run.batch_size,
+        repeat_index,
+        duration_secs,
+        documents,
+        docs_per_sec,
+        latency,
+    })
+}
+
+// Nearest-rank percentile over a slice already sorted ascending.
+fn percentiles(sorted: &[std::time::Duration]) -> LatencyPercentiles {
+    let pick = |p: f64| -> f64 {
+        if sorted.is_empty() {
+            return 0.0;
+        }
+        let rank = ((p * sorted.len() as f64).ceil() as usize)
+            .saturating_sub(1)
+            .min(sorted.len() - 1);
+        sorted[rank].as_secs_f64() * 1000.0
+    };
+
+    LatencyPercentiles {
+        p50_ms: pick(0.50),
+        p95_ms: pick(0.95),
+        p99_ms: pick(0.99),
+    }
+}
@@ -0,0 +1,213 @@
+use std::fs;
+use std::hash::Hasher;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+/// Identifies the exact import configuration a ledger was written for, so a
+/// changed input file, target, batch size, or thread count invalidates the
+/// stale checkpoint. The input is fingerprinted by content hash rather than
+/// length, so a same-size edit still invalidates it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+struct LedgerKey {
+    input: PathBuf,
+    input_hash: u64,
+    target: String,
+    batch_size: usize,
+    threads: usize,
+}
+
+// Cheap whole-file fingerprint used to detect an edited input. Not
+// cryptographic, just enough to invalidate a stale checkpoint.
+fn hash_file(path: &Path) -> io::Result<u64> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.write(&buf[..n]);
+    }
+    Ok(hasher.finish())
+}
+
+// A half-open `[start, end)` range of record indices that has been committed.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+struct RecordRange {
+    start: u64,
+    end: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct LedgerFile {
+    key: Option<LedgerKey>,
+    done: Vec<RecordRange>,
+}
+
+/// Tracks which record ranges of an import have already been committed, so a
+/// re-run can skip them instead of re-inserting the whole file. Backed by a
+/// sidecar `<input>.progress.json` file, periodically flushed atomically --
+/// unless opened with `enabled: false`, in which case it's in-memory only.
+pub struct Checkpoint {
+    ledger_path: PathBuf,
+    inner: Mutex<LedgerFile>,
+    pending_flushes: Mutex<usize>,
+    enabled: bool,
+}
+
+// How many committed ranges accumulate before the ledger is rewritten to
+// disk. Bounds the number of full-file rewrites on a large import to
+// roughly `total_chunks / FLUSH_EVERY` instead of one per chunk.
+const FLUSH_EVERY: usize = 20;
+
+impl Checkpoint {
+    /// Open (or create) the ledger for this import. `enabled` gates whether
+    /// a sidecar ledger is touched at all; passing `false` (used by `bench`,
+    /// which intentionally replays the same input/target `repeat` times)
+    /// gives an in-memory `Checkpoint` that never reports anything as done.
+    pub fn open(
+        input: &Path,
+        target: &str,
+        batch_size: usize,
+        threads: usize,
+        enabled: bool,
+    ) -> io::Result<Self> {
+        if !enabled {
+            return Ok(Checkpoint {
+                ledger_path: PathBuf::new(),
+                inner: Mutex::new(LedgerFile::default()),
+                pending_flushes: Mutex::new(0),
+                enabled: false,
+            });
+        }
+
+        let input_hash = hash_file(input)?;
+        let key = LedgerKey {
+            input: input.to_path_buf(),
+            input_hash,
+            target: target.to_string(),
+            batch_size,
+            threads,
+        };
+        let ledger_path = ledger_path_for(input);
+
+        let existing = fs::read_to_string(&ledger_path)
+            .ok()
+            .and_then(|contents| serde_json::from_str::<LedgerFile>(&contents).ok());
+
+        let inner = match existing {
+            // Same input/target/batch-size/thread-count: safe to resume.
+            Some(file) if file.key.as_ref() == Some(&key) => file,
+            // Missing, unreadable, or written for a different configuration:
+            // start a fresh ledger rather than trusting stale offsets.
+            _ => LedgerFile {
+                key: Some(key),
+                done: Vec::new(),
+            },
+        };
+
+        Ok(Checkpoint {
+            ledger_path,
+            inner: Mutex::new(inner),
+            pending_flushes: Mutex::new(0),
+            enabled: true,
+        })
+    }
+
+    /// Whether the half-open record range `[start, end)` is already fully
+    /// covered by a previously committed range. Always `false` when
+    /// checkpointing is disabled.
+    pub fn is_done(&self, start: u64, end: u64) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        self.inner
+            .lock()
+            .unwrap()
+            .done
+            .iter()
+            .any(|r| r.start <= start && end <= r.end)
+    }
+
+    /// Record `[start, end)` as committed. Coalesces with adjacent/overlapping
+    /// ranges and flushes to disk every `FLUSH_EVERY` commits (and once more
+    /// on drop) rather than on every one.
+    pub fn mark_done(&self, start: u64, end: u64) -> io::Result<()> {
+        if !self.enabled {
+            return Ok(());
+        }
+        let mut inner = self.inner.lock().unwrap();
+        insert_coalesced(&mut inner.done, RecordRange { start, end });
+
+        let mut pending = self.pending_flushes.lock().unwrap();
+        *pending += 1;
+        if *pending >= FLUSH_EVERY {
+            *pending = 0;
+            flush(&self.ledger_path, &inner)?;
+        }
+        Ok(())
+    }
+
+    /// Number of committed ranges and total records they cover, for the
+    /// resumed-vs-remaining summary printed on startup.
+    pub fn summary(&self) -> (usize, u64) {
+        let inner = self.inner.lock().unwrap();
+        let records = inner.done.iter().map(|r| r.end - r.start).sum();
+        (inner.done.len(), records)
+    }
+}
+
+impl Drop for Checkpoint {
+    fn drop(&mut self) {
+        // Flush any commits made since the last periodic flush. Lock `inner`
+        // before `pending_flushes`, same order as `mark_done`, to avoid a
+        // lock-order inversion.
+        if !self.enabled {
+            return;
+        }
+        let inner = self.inner.lock().unwrap();
+        if *self.pending_flushes.lock().unwrap() > 0 {
+            if let Err(e) = flush(&self.ledger_path, &inner) {
+                eprintln!("Checkpoint: failed to flush final ledger state: {}", e);
+            }
+        }
+    }
+}
+
+// Insert `range` into `done`, merging it with any existing range it overlaps
+// or touches, so the ledger grows with distinct committed spans instead of
+// one entry per chunk.
+fn insert_coalesced(done: &mut Vec<RecordRange>, range: RecordRange) {
+    done.push(range);
+    done.sort_by_key(|r| r.start);
+
+    let mut merged: Vec<RecordRange> = Vec::with_capacity(done.len());
+    for r in done.drain(..) {
+        match merged.last_mut() {
+            Some(last) if r.start <= last.end => last.end = last.end.max(r.end),
+            _ => merged.push(r),
+        }
+    }
+    *done = merged;
+}
+
+fn flush(ledger_path: &Path, inner: &LedgerFile) -> io::Result<()> {
+    // Write to a temp file and rename over the ledger so a crash mid-write
+    // never leaves a truncated/corrupt ledger behind.
+    let mut tmp_name = ledger_path.as_os_str().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_name);
+    let data = serde_json::to_string_pretty(inner).expect("serialize ledger");
+    fs::write(&tmp_path, data)?;
+    fs::rename(&tmp_path, ledger_path)
+}
+
+fn ledger_path_for(input: &Path) -> PathBuf {
+    let mut name = input.as_os_str().to_os_string();
+    name.push(".progress.json");
+    PathBuf::from(name)
+}
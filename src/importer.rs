@@ -0,0 +1,169 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+
+use crate::checkpoint::Checkpoint;
+use crate::types::ArxivEntry;
+
+pub type ImportResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+/// Common surface every backend (SurrealDB, Meilisearch, ...) implements so the
+/// parallel insert loop can run against any of them unchanged.
+#[async_trait::async_trait]
+pub trait Importer: Send + Sync + 'static {
+    type Config: Send + Sync;
+
+    /// Build (and authenticate, if needed) a connection to the backend.
+    async fn connect(config: &Self::Config) -> ImportResult<Self>
+    where
+        Self: Sized;
+
+    /// Insert one batch of entries into `target` (a table or index name).
+    async fn insert_batch(&self, target: &str, batch: &[ArxivEntry]) -> ImportResult<()>;
+}
+
+// Base delay and ceiling for the exponential backoff used between insert retries.
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(200);
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(10);
+
+/// Records the wall-clock duration of every `insert_batch` call so a caller
+/// (currently the `bench` subcommand) can report latency percentiles. A no-op
+/// outside of benchmarking: normal imports never construct one.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    latencies: Mutex<Vec<Duration>>,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Metrics::default()
+    }
+
+    fn record(&self, elapsed: Duration) {
+        self.latencies.lock().unwrap().push(elapsed);
+    }
+
+    /// Snapshot of recorded latencies, sorted ascending.
+    pub fn sorted_latencies(&self) -> Vec<Duration> {
+        let mut latencies = self.latencies.lock().unwrap().clone();
+        latencies.sort();
+        latencies
+    }
+}
+
+/// A small pool of already-connected backends, built once up front and shared
+/// across worker tasks so a slice insert no longer pays for a fresh connection
+/// on every batch. Workers round-robin over the pool by thread id.
+pub struct ConnectionPool<I: Importer> {
+    connections: Vec<std::sync::Arc<I>>,
+}
+
+impl<I: Importer> ConnectionPool<I> {
+    pub async fn new(config: &I::Config, size: usize) -> ImportResult<Self> {
+        let mut connections = Vec::with_capacity(size);
+        for _ in 0..size {
+            connections.push(std::sync::Arc::new(I::connect(config).await?));
+        }
+        Ok(ConnectionPool { connections })
+    }
+
+    pub fn get(&self, thread_id: usize) -> std::sync::Arc<I> {
+        std::sync::Arc::clone(&self.connections[(thread_id - 1) % self.connections.len()])
+    }
+}
+
+/// Insert a whole worker slice, split into `batch_size`-sized chunks so a
+/// single request stays within the backend's payload limits. Each chunk is
+/// retried independently with exponential backoff rather than losing the
+/// whole slice on a transient failure. `base_offset` is the record index
+/// (within the whole input) that `item` starts at, used to address chunks in
+/// the checkpoint ledger; a chunk already marked done there is skipped.
+/// Returns the number of records actually inserted (excluding skipped ones).
+/// `metrics`, when present, records the wall-clock duration of every
+/// `insert_batch` call for later latency reporting.
+#[allow(clippy::too_many_arguments)]
+pub async fn insert_slice<I: Importer>(
+    importer: &I,
+    checkpoint: &Checkpoint,
+    metrics: Option<&Metrics>,
+    thread_id: usize,
+    target: &str,
+    base_offset: u64,
+    batch_size: usize,
+    max_retries: u32,
+    item: &[ArxivEntry],
+) -> ImportResult<u64> {
+    if item.is_empty() {
+        println!("Thread {}: No items to insert", thread_id);
+        return Ok(0);
+    }
+
+    println!(
+        "Thread {}: Inserting {} items into {} in chunks of {}",
+        thread_id,
+        item.len(),
+        target,
+        batch_size
+    );
+
+    let mut inserted = 0u64;
+    for (i, chunk) in item.chunks(batch_size.max(1)).enumerate() {
+        let start = base_offset + (i * batch_size) as u64;
+        let end = start + chunk.len() as u64;
+
+        if checkpoint.is_done(start, end) {
+            println!(
+                "Thread {}: Skipping already-committed records {}..{}",
+                thread_id, start, end
+            );
+            continue;
+        }
+
+        insert_chunk_with_retry(importer, metrics, thread_id, target, chunk, max_retries).await?;
+        checkpoint.mark_done(start, end)?;
+        inserted += chunk.len() as u64;
+    }
+
+    Ok(inserted)
+}
+
+async fn insert_chunk_with_retry<I: Importer>(
+    importer: &I,
+    metrics: Option<&Metrics>,
+    thread_id: usize,
+    target: &str,
+    chunk: &[ArxivEntry],
+    max_retries: u32,
+) -> ImportResult<()> {
+    let mut attempt = 0;
+    loop {
+        let started = Instant::now();
+        let result = importer.insert_batch(target, chunk).await;
+        if let Some(metrics) = metrics {
+            metrics.record(started.elapsed());
+        }
+
+        match result {
+            Ok(_) => return Ok(()),
+            Err(e) if attempt < max_retries => {
+                let backoff = RETRY_BASE_DELAY * 2u32.saturating_pow(attempt);
+                let jitter = Duration::from_millis(rand::rng().random_range(0..50));
+                let delay = (backoff + jitter).min(RETRY_MAX_DELAY);
+                attempt += 1;
+                eprintln!(
+                    "Thread {}: Batch insert failed (attempt {}/{}): {}. Retrying in {:?}",
+                    thread_id, attempt, max_retries, e, delay
+                );
+                tokio::time::sleep(delay).await;
+            }
+            Err(e) => {
+                eprintln!(
+                    "Thread {}: Batch insert failed after {} retries: {}",
+                    thread_id, max_retries, e
+                );
+                return Err(e);
+            }
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use std::io::{BufRead, BufReader, Read};
+use std::path::PathBuf;
+
+use rand::Rng;
+use tokio::sync::mpsc;
+
+use crate::types::ArxivEntry;
+
+// Whether the input file is a single JSON array of records, or newline-delimited
+// JSON (one record per line). Sniffed from the first non-whitespace byte.
+pub enum DataFormat {
+    Array,
+    Ndjson,
+}
+
+// Peek at the first non-whitespace byte of the input file to tell a single
+// JSON array apart from newline-delimited JSON, without reading the whole
+// file into memory.
+pub fn sniff_format(path: &PathBuf) -> std::io::Result<DataFormat> {
+    let file = std::fs::File::open(path)?;
+    let mut reader = BufReader::new(file);
+    let mut byte = [0u8; 1];
+    loop {
+        if reader.read(&mut byte)? == 0 {
+            // Empty file: treat as NDJSON so the stream just yields nothing.
+            return Ok(DataFormat::Ndjson);
+        }
+        if byte[0].is_ascii_whitespace() {
+            continue;
+        }
+        return Ok(if byte[0] == b'[' {
+            DataFormat::Array
+        } else {
+            DataFormat::Ndjson
+        });
+    }
+}
+
+// Load JSON data from a file (array-of-objects fallback path)
+pub fn load_data(path: &PathBuf) -> Vec<ArxivEntry> {
+    let data = std::fs::read_to_string(path).expect("Failed to read file");
+    let data: Vec<ArxivEntry> = serde_json::from_str(&data).expect("Failed to parse JSON");
+    data
+}
+
+// Stream newline-delimited JSON from `path`, batching up to `batch_size`
+// parsed records per send. Runs on a blocking thread since file IO here is
+// synchronous; a malformed line is logged with its line number and skipped
+// rather than aborting the whole import. Each batch is tagged with the
+// record offset (among valid records only) it starts at, so a checkpoint
+// ledger can address it.
+pub fn stream_ndjson(
+    path: &PathBuf,
+    batch_size: usize,
+    tx: mpsc::Sender<(u64, Vec<ArxivEntry>)>,
+) -> std::io::Result<()> {
+    let file = std::fs::File::open(path)?;
+    let reader = BufReader::new(file);
+
+    let mut batch = Vec::with_capacity(batch_size);
+    let mut batch_start: u64 = 0;
+    let mut record_offset: u64 = 0;
+    for (line_no, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            // Skip blank lines, including a trailing empty line. A truncated
+            // line is not blank; it fails to parse and is skipped below.
+            continue;
+        }
+
+        match serde_json::from_str::<ArxivEntry>(&line) {
+            Ok(entry) => {
+                batch.push(entry);
+                record_offset += 1;
+                if batch.len() >= batch_size {
+                    let full_batch = std::mem::replace(&mut batch, Vec::with_capacity(batch_size));
+                    if tx.blocking_send((batch_start, full_batch)).is_err() {
+                        // Workers are gone; no point reading the rest of the file.
+                        return Ok(());
+                    }
+                    batch_start = record_offset;
+                }
+            }
+            Err(e) => eprintln!("Line {}: skipping malformed record: {}", line_no + 1, e),
+        }
+    }
+
+    if !batch.is_empty() {
+        let _ = tx.blocking_send((batch_start, batch));
+    }
+
+    Ok(())
+}
+
+// Start/end record indices (half-open) a thread's slice covers.
+pub fn slice_bounds(len: usize, thread: usize, num_threads: usize) -> (usize, usize) {
+    let start = (thread - 1) * len / num_threads;
+    let end = thread * len / num_threads;
+    (start, end)
+}
+
+// Given a thread, get a slice of the data starting from the thread's index.
+// Takes `data` by reference so callers don't pay for cloning the whole
+// dataset just to then clone one slice out of it.
+pub fn get_slice(data: &[ArxivEntry], thread: usize, num_threads: usize) -> Vec<ArxivEntry> {
+    let (start, end) = slice_bounds(data.len(), thread, num_threads);
+    data[start..end].to_vec()
+}
+
+// Generate a random 5 letter string, used as a fallback table/index name
+pub fn generate_random_string() -> String {
+    let chars = "abcdefghijklmnopqrstuvwxyz";
+    let random_string: String = (0..5)
+        .map(|_| {
+            let idx = rand::rng().random_range(0..chars.len());
+            chars.chars().nth(idx).unwrap()
+        })
+        .collect();
+    random_string
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A partition is correct when every thread's [start, end) range is
+    // contiguous with its neighbours and the whole run covers exactly
+    // `0..len` with no gap, overlap, or dropped record.
+    fn assert_partition_covers_everything(len: usize, num_threads: usize) {
+        let mut prev_end = 0usize;
+        for thread in 1..=num_threads {
+            let (start, end) = slice_bounds(len, thread, num_threads);
+            assert_eq!(
+                start, prev_end,
+                "thread {} should start where thread {} left off",
+                thread,
+                thread - 1
+            );
+            assert!(end >= start, "thread {} has a negative-length range", thread);
+            prev_end = end;
+        }
+        assert_eq!(prev_end, len, "partition must cover every record exactly once");
+    }
+
+    #[test]
+    fn partitions_evenly_divisible_input() {
+        assert_partition_covers_everything(100, 4);
+    }
+
+    #[test]
+    fn partitions_input_not_divisible_by_thread_count() {
+        assert_partition_covers_everything(10, 3);
+    }
+
+    #[test]
+    fn partitions_fewer_records_than_threads() {
+        // Some threads end up with an empty [start, end) range, but nothing
+        // is dropped or double-counted.
+        assert_partition_covers_everything(2, 5);
+    }
+
+    #[test]
+    fn partitions_empty_input() {
+        assert_partition_covers_everything(0, 4);
+    }
+
+    #[test]
+    fn partitions_single_thread() {
+        assert_partition_covers_everything(7, 1);
+    }
+
+    use crate::test_support::{sample_entry_json, write_temp_ndjson};
+
+    #[tokio::test]
+    async fn stream_ndjson_skips_malformed_lines_and_delivers_valid_records() {
+        let entry1 = sample_entry_json(1);
+        let entry2 = sample_entry_json(2);
+        let lines = [
+            entry1.as_str(),
+            "not json at all",
+            "", // blank line
+            entry2.as_str(),
+            r#"{"id": "truncated"#, // truncated trailing line
+        ];
+        let path = write_temp_ndjson(&lines);
+
+        let (tx, mut rx) = mpsc::channel(4);
+        let batch_size = 10;
+        let path_for_producer = path.clone();
+        let producer =
+            tokio::task::spawn_blocking(move || stream_ndjson(&path_for_producer, batch_size, tx));
+
+        let mut received = Vec::new();
+        while let Some((_, batch)) = rx.recv().await {
+            received.extend(batch);
+        }
+        producer
+            .await
+            .expect("producer task panicked")
+            .expect("producer IO failed");
+
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[0].id, 1);
+        assert_eq!(received[1].id, 2);
+    }
+}
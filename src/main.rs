@@ -1,24 +1,86 @@
+mod bench;
+mod checkpoint;
+mod importer;
+mod loader;
+#[cfg(feature = "meili")]
+mod meili;
+#[cfg(test)]
+mod mock;
+#[cfg(feature = "surreal")]
+mod surreal;
+#[cfg(test)]
+mod test_support;
+mod types;
+
 use std::path::PathBuf;
+use std::sync::Arc;
+
+use clap::{Args, Parser, Subcommand};
+use tokio::sync::Mutex;
+use tokio::sync::mpsc;
 
-use clap::Parser;
-use rand::Rng;
-use surrealdb::engine::remote::ws::Ws;
-use surrealdb::opt::auth::Root;
-use surrealdb::{Surreal, engine::remote::ws::Client};
+use checkpoint::Checkpoint;
+use importer::{ConnectionPool, ImportResult, Importer, Metrics};
+use loader::DataFormat;
+use types::ArxivEntry;
 
-#[derive(Parser)]
+#[cfg(feature = "meili")]
+use meili::{MeiliConfig, MeiliImporter};
+#[cfg(feature = "surreal")]
+use surreal::{SurrealConfig, SurrealImporter};
+
+#[derive(Parser, Debug)]
 #[command(name = "Parallel Surrealdb Import")]
 #[command(bin_name = "parallel-surreal")]
 #[command(color = clap::ColorChoice::Always)]
-#[command(about = "Import data into SurrealDB in parallel")]
+#[command(about = "Import data into SurrealDB or Meilisearch in parallel")]
 #[command(author = "Salvador Guzman")]
 #[command(version = "1.0")]
 #[command(long_about = None)]
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct Cli {
-    /// Sets a custom config file
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Import into SurrealDB
+    #[cfg(feature = "surreal")]
+    Surreal(SurrealArgs),
+
+    /// Import into Meilisearch
+    #[cfg(feature = "meili")]
+    Meili(MeiliArgs),
+
+    /// Replay a workload file and report throughput/latency
+    Bench(bench::BenchArgs),
+}
+
+// Arguments shared by every backend
+#[derive(Args, Debug, Clone)]
+pub(crate) struct CommonArgs {
+    /// Path to the input file (JSON array or newline-delimited JSON)
     #[arg(long)]
-    input: PathBuf,
+    pub(crate) input: PathBuf,
+
+    // Number of threads
+    #[arg(long, default_value_t = 8)]
+    pub(crate) threads: usize,
+
+    // Number of records sent per insert request
+    #[arg(long, default_value_t = 500)]
+    pub(crate) batch_size: usize,
+
+    // Maximum number of retries for a failed batch insert before giving up on it
+    #[arg(long, default_value_t = 5)]
+    pub(crate) max_retries: u32,
+}
+
+#[cfg(feature = "surreal")]
+#[derive(Args, Debug, Clone)]
+struct SurrealArgs {
+    #[command(flatten)]
+    common: CommonArgs,
 
     // Name of surreal user
     #[arg(long, default_value = "root")]
@@ -47,214 +109,215 @@ struct Cli {
     // Name of surreal table
     #[arg(long)]
     table: Option<String>,
-
-    // Number of threads
-    #[arg(long, default_value_t = 8)]
-    threads: usize,
 }
 
-use serde::{Deserialize, Serialize};
-
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
-pub struct ArxivEntry {
-    // Since abstract is a reserved word in Rust, we use `abstract_text` instead
-    #[serde(rename = "abstract")]
-    pub abstract_text: Option<String>,
-    pub authors: Option<String>,
-    pub authors_parsed: Vec<Vec<String>>,
-    pub categories: Option<String>,
-    pub comments: Option<String>,
-    pub doi: Option<String>,
-    pub id: u32,
-    pub journal_ref: Option<String>,
-    pub license: Option<String>,
-    pub report_no: Option<String>,
-    pub submitter: Option<String>,
-    pub title: Option<String>,
-    pub update_date: Option<String>,
-    pub versions: Vec<Version>,
-}
+#[cfg(feature = "meili")]
+#[derive(Args, Debug, Clone)]
+struct MeiliArgs {
+    #[command(flatten)]
+    common: CommonArgs,
 
-// ArxivEntry struct without the id field
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
-pub struct ArxivEntry2 {
-    // Since abstract is a reserved word in Rust, we use `abstract_text` instead
-    #[serde(rename = "abstract")]
-    pub abstract_text: Option<String>,
-    pub authors: Option<String>,
-    pub authors_parsed: Vec<Vec<String>>,
-    pub categories: Option<String>,
-    pub comments: Option<String>,
-    pub doi: Option<String>,
-    pub journal_ref: Option<String>,
-    pub license: Option<String>,
-    pub report_no: Option<String>,
-    pub submitter: Option<String>,
-    pub title: Option<String>,
-    pub update_date: Option<String>,
-    pub versions: Vec<Version>,
-}
+    // Meilisearch base URL, e.g. http://127.0.0.1:7700
+    #[arg(long)]
+    url: String,
 
-// Map the ArxivEntry struct to the ArxivEntry2 struct
-impl From<ArxivEntry> for ArxivEntry2 {
-    fn from(entry: ArxivEntry) -> Self {
-        ArxivEntry2 {
-            abstract_text: entry.abstract_text,
-            authors: entry.authors,
-            authors_parsed: entry.authors_parsed,
-            categories: entry.categories,
-            comments: entry.comments,
-            doi: entry.doi,
-            journal_ref: entry.journal_ref,
-            license: entry.license,
-            report_no: entry.report_no,
-            submitter: entry.submitter,
-            title: entry.title,
-            update_date: entry.update_date,
-            versions: entry.versions,
-        }
-    }
-}
+    // Meilisearch API key
+    #[arg(long)]
+    api_key: String,
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
-pub struct Version {
-    pub created: String,
-    pub version: String,
+    // Name of the meilisearch index
+    #[arg(long)]
+    index: Option<String>,
 }
 
 #[tokio::main(flavor = "current_thread")]
-async fn main() -> surrealdb::Result<()> {
+async fn main() -> ImportResult<()> {
     let cli = Cli::parse();
 
-    // Random table name
-    let table = generate_random_string();
-
-    // Print table name
-    println!("Table name: {}", table);
-
-    // Print the input data
-    println!("Arguments: {:#?}", cli);
-
-    let json_data = load_data(&cli.input);
-
-    // Create a vector to hold all the task handles
-    let mut tasks = Vec::new();
-    let num_threads = cli.threads.clone();
-    let table = if cli.table.is_some() {
-        cli.table.clone().unwrap()
-    } else {
-        table
-    };
-
-    for i in 1..=cli.threads {
-        let table = table.clone();
-        let cli = cli.clone();
-
-        let slice = get_slice(json_data.clone(), i, num_threads);
-        let task = tokio::spawn(async move {
-            match insert_items(i, table.clone(), &cli, &slice).await {
-                Ok(_) => println!("Thread {}: Done", i),
-                Err(e) => eprintln!("Thread {}: Failed to insert items: {}", i, e),
-            }
-        });
-        tasks.push(task);
-    }
-
-    // Wait for all tasks to complete
-    for task in tasks {
-        if let Err(e) = task.await {
-            eprintln!("Task failed: {}", e);
-        }
-    }
-
-    println!("Goodbye, world!");
-    Ok(())
-}
-
-// Task of insertion into SurrealDB a single item
-// Should initialize a new client
-// and insert the item into the index
-async fn insert_items(
-    thread_id: usize,
-    table: String,
-    cli: &Cli,
-    item: &Vec<ArxivEntry>,
-) -> Result<(), surrealdb::Error> {
-    if item.is_empty() {
-        // Use is_empty() for clarity
-        println!("Thread {}: No items to insert", thread_id);
-        return Ok(());
-    }
-
-    // Convert the Vec<ArxivEntry> to Vec<ArxivEntry2>
-    let item: Vec<ArxivEntry2> = item.iter().map(|e| e.clone().into()).collect();
-
-    println!(
-        "Thread {}: Inserting {} items into index {}",
-        thread_id,
-        item.len(),
-        table
-    );
-    let db = build_connection(cli).await; // Use ? to propagate potential connection errors
-
-    // Match the result instead of unwrapping
-    match db
-        .insert::<Vec<ArxivEntry2>>(table)
-        .content(item.clone())
-        .await
-    {
-        Ok(_) => {
-            // Optional: Log success if needed
-            // println!("Thread {}: Successfully inserted {} items", thread_id, item.len());
+    match cli.command {
+        #[cfg(feature = "surreal")]
+        Command::Surreal(args) => {
+            let target = args.table.clone();
+            let common = args.common.clone();
+            let config = SurrealConfig {
+                host: args.host,
+                port: args.port,
+                user: args.user,
+                password: args.password,
+                ns: args.ns,
+                db: args.db,
+            };
+            run::<SurrealImporter>(common, config, target, None, true).await?;
             Ok(())
         }
-        Err(e) => {
-            eprintln!("Thread {}: Failed insertion: {}", thread_id, e);
-            Err(e) // Propagate the error
+        #[cfg(feature = "meili")]
+        Command::Meili(args) => {
+            let target = args.index.clone();
+            let common = args.common.clone();
+            let config = MeiliConfig {
+                url: args.url,
+                api_key: args.api_key,
+            };
+            run::<MeiliImporter>(common, config, target, None, true).await?;
+            Ok(())
         }
+        Command::Bench(args) => bench::run_bench(args).await,
     }
 }
 
-// Generate a random 5 letter string
-fn generate_random_string() -> String {
-    let chars = "abcdefghijklmnopqrstuvwxyz";
-    let random_string: String = (0..5)
-        .map(|_| {
-            let idx = rand::rng().random_range(0..chars.len());
-            chars.chars().nth(idx).unwrap()
-        })
-        .collect();
-    random_string
-}
-
-// Load JSON data from a file
-fn load_data(path: &PathBuf) -> Vec<ArxivEntry> {
-    let data = std::fs::read_to_string(path).expect("Failed to read file");
-    let data: Vec<ArxivEntry> = serde_json::from_str(&data).expect("Failed to parse JSON");
-    data
-}
+// Shared parallel import loop: drives any `Importer` backend over either a
+// fully-materialized array of records or a streamed NDJSON input. Returns the
+// number of records actually inserted (excluding ones a checkpoint skipped).
+// `metrics`, when present, records per-`insert_batch` latencies for the
+// `bench` subcommand. `resumable` controls whether a `<input>.progress.json`
+// checkpoint ledger is used at all: `bench` passes `false` since it replays
+// the same input against the same target `repeat` times on purpose, and a
+// real checkpoint would mark every record done on the first repeat and make
+// every subsequent one insert (and measure) nothing.
+pub(crate) async fn run<I: Importer>(
+    common: CommonArgs,
+    config: I::Config,
+    target: Option<String>,
+    metrics: Option<Arc<Metrics>>,
+    resumable: bool,
+) -> ImportResult<u64> {
+    let target = target.unwrap_or_else(loader::generate_random_string);
+    println!("Target: {}", target);
+    println!("Arguments: {:#?}", common);
+
+    let num_threads = common.threads;
+    let pool = Arc::new(ConnectionPool::<I>::new(&config, num_threads).await?);
+
+    let checkpoint = Arc::new(Checkpoint::open(
+        &common.input,
+        &target,
+        common.batch_size,
+        num_threads,
+        resumable,
+    )?);
+    if resumable {
+        let (resumed_ranges, resumed_records) = checkpoint.summary();
+        println!(
+            "Resuming: {} range(s) already committed, {} records to skip",
+            resumed_ranges, resumed_records
+        );
+    }
 
-// Given a thread, get a slice of the data starting from the thread's index
-fn get_slice(data: Vec<ArxivEntry>, thread: usize, num_threads: usize) -> Vec<ArxivEntry> {
-    let start = (thread - 1) * data.len() / num_threads;
-    let end = thread * data.len() / num_threads;
-    data[start..end].to_vec()
-}
+    let mut total_inserted = 0u64;
+
+    match loader::sniff_format(&common.input).expect("Failed to read input file") {
+        DataFormat::Array => {
+            let json_data = loader::load_data(&common.input);
+
+            let mut tasks = Vec::new();
+            for i in 1..=num_threads {
+                let target = target.clone();
+                let db = pool.get(i);
+                let checkpoint = Arc::clone(&checkpoint);
+                let metrics = metrics.clone();
+                let batch_size = common.batch_size;
+                let max_retries = common.max_retries;
+
+                let (start, _) = loader::slice_bounds(json_data.len(), i, num_threads);
+                let slice = loader::get_slice(&json_data, i, num_threads);
+                let task = tokio::spawn(async move {
+                    importer::insert_slice(
+                        &*db,
+                        &checkpoint,
+                        metrics.as_deref(),
+                        i,
+                        &target,
+                        start as u64,
+                        batch_size,
+                        max_retries,
+                        &slice,
+                    )
+                    .await
+                });
+                tasks.push(task);
+            }
 
-async fn build_connection(cli: &Cli) -> Surreal<Client> {
-    let address = format!("{}:{}", cli.host, cli.port);
-    let db = Surreal::new::<Ws>(address).await.unwrap();
+            for (i, task) in (1..=num_threads).zip(tasks) {
+                match task.await {
+                    Ok(Ok(inserted)) => {
+                        total_inserted += inserted;
+                        println!("Thread {}: Done", i);
+                    }
+                    Ok(Err(e)) => eprintln!("Thread {}: Failed to insert items: {}", i, e),
+                    Err(e) => eprintln!("Task failed: {}", e),
+                }
+            }
+        }
+        DataFormat::Ndjson => {
+            // Bound the channel so a slow set of workers applies backpressure
+            // to the producer instead of the whole file piling up in memory.
+            let (tx, rx) = mpsc::channel::<(u64, Vec<ArxivEntry>)>(num_threads * 2);
+            let rx = Arc::new(Mutex::new(rx));
+
+            let input = common.input.clone();
+            let batch_size = common.batch_size;
+            let producer =
+                tokio::task::spawn_blocking(move || loader::stream_ndjson(&input, batch_size, tx));
+
+            let mut tasks = Vec::new();
+            for i in 1..=num_threads {
+                let target = target.clone();
+                let db = pool.get(i);
+                let checkpoint = Arc::clone(&checkpoint);
+                let metrics = metrics.clone();
+                let batch_size = common.batch_size;
+                let max_retries = common.max_retries;
+                let rx = Arc::clone(&rx);
+
+                let task = tokio::spawn(async move {
+                    let mut inserted = 0u64;
+                    loop {
+                        let next = rx.lock().await.recv().await;
+                        match next {
+                            Some((offset, batch)) => match importer::insert_slice(
+                                &*db,
+                                &checkpoint,
+                                metrics.as_deref(),
+                                i,
+                                &target,
+                                offset,
+                                batch_size,
+                                max_retries,
+                                &batch,
+                            )
+                            .await
+                            {
+                                Ok(count) => {
+                                    inserted += count;
+                                    println!("Thread {}: Done with a batch", i);
+                                }
+                                Err(e) => {
+                                    eprintln!("Thread {}: Failed to insert items: {}", i, e)
+                                }
+                            },
+                            None => break,
+                        }
+                    }
+                    inserted
+                });
+                tasks.push(task);
+            }
 
-    // Signin as a namespace, database, or root user
-    db.signin(Root {
-        username: &cli.user,
-        password: &cli.password,
-    })
-    .await
-    .unwrap();
+            for (i, task) in (1..=num_threads).zip(tasks) {
+                match task.await {
+                    Ok(inserted) => total_inserted += inserted,
+                    Err(e) => eprintln!("Thread {} task failed: {}", i, e),
+                }
+            }
 
-    // Select a specific namespace / database
-    db.use_ns(&cli.ns).use_db(&cli.db).await.unwrap();
+            match producer.await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => eprintln!("Producer: Failed to stream input: {}", e),
+                Err(e) => eprintln!("Producer task failed: {}", e),
+            }
+        }
+    }
 
-    db
+    println!("Goodbye, world!");
+    Ok(total_inserted)
 }
@@ -0,0 +1,32 @@
+use meilisearch_sdk::client::Client;
+
+use crate::importer::{ImportResult, Importer};
+use crate::types::ArxivEntry;
+
+#[derive(Debug, Clone)]
+pub struct MeiliConfig {
+    pub url: String,
+    pub api_key: String,
+}
+
+pub struct MeiliImporter {
+    client: Client,
+}
+
+#[async_trait::async_trait]
+impl Importer for MeiliImporter {
+    type Config = MeiliConfig;
+
+    async fn connect(config: &MeiliConfig) -> ImportResult<Self> {
+        let client = Client::new(config.url.clone(), Some(config.api_key.clone()));
+        Ok(MeiliImporter { client })
+    }
+
+    async fn insert_batch(&self, target: &str, batch: &[ArxivEntry]) -> ImportResult<()> {
+        self.client
+            .index(target)
+            .add_documents(batch, Some("id"))
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+use std::sync::{Arc, Mutex};
+
+use crate::importer::{ImportResult, Importer};
+use crate::types::ArxivEntry;
+
+/// Test double for `Importer` that records every batch it receives into a
+/// shared `Vec` instead of talking to a real server, so the partitioning and
+/// streaming logic can be exercised without SurrealDB/Meilisearch running.
+#[derive(Clone, Default)]
+pub struct MockImporter {
+    received: Arc<Mutex<Vec<ArxivEntry>>>,
+}
+
+#[async_trait::async_trait]
+impl Importer for MockImporter {
+    type Config = ();
+
+    async fn connect(_config: &()) -> ImportResult<Self> {
+        Ok(MockImporter::default())
+    }
+
+    async fn insert_batch(&self, _target: &str, batch: &[ArxivEntry]) -> ImportResult<()> {
+        self.received.lock().unwrap().extend_from_slice(batch);
+        Ok(())
+    }
+}
+
+impl MockImporter {
+    pub fn received(&self) -> Vec<ArxivEntry> {
+        self.received.lock().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkpoint::Checkpoint;
+    use crate::importer::insert_slice;
+    use crate::loader::{self, get_slice, slice_bounds};
+    use crate::test_support::{sample_entry_json, write_temp_ndjson};
+    use std::path::PathBuf;
+    use tokio::sync::mpsc;
+
+    #[tokio::test]
+    async fn streaming_loader_delivers_every_valid_record_to_the_mock_importer() {
+        let entries: Vec<String> = (1..=3).map(sample_entry_json).collect();
+        let lines = [
+            entries[0].as_str(),
+            "garbage",
+            "",
+            entries[1].as_str(),
+            entries[2].as_str(),
+        ];
+        let path = write_temp_ndjson(&lines);
+
+        let (tx, mut rx) = mpsc::channel(4);
+        let batch_size = 2;
+        let path_for_producer = path.clone();
+        let producer = tokio::task::spawn_blocking(move || {
+            loader::stream_ndjson(&path_for_producer, batch_size, tx)
+        });
+
+        let mock = MockImporter::default();
+        let checkpoint =
+            Checkpoint::open(&path, "mock", batch_size, 1, true).expect("open checkpoint ledger");
+
+        while let Some((offset, batch)) = rx.recv().await {
+            insert_slice(&mock, &checkpoint, None, 1, "mock", offset, batch_size, 0, &batch)
+                .await
+                .expect("insert into mock importer");
+        }
+        producer
+            .await
+            .expect("producer task panicked")
+            .expect("producer IO failed");
+
+        let ledger_path = {
+            let mut name = path.as_os_str().to_os_string();
+            name.push(".progress.json");
+            PathBuf::from(name)
+        };
+        std::fs::remove_file(&path).ok();
+        std::fs::remove_file(&ledger_path).ok();
+
+        let received = mock.received();
+        assert_eq!(received.len(), 3);
+        assert_eq!(
+            received.iter().map(|e| e.id).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn get_slice_matches_slice_bounds_for_every_thread() {
+        let data: Vec<ArxivEntry> = (1..=10)
+            .map(|id| serde_json::from_str(&sample_entry_json(id)).unwrap())
+            .collect();
+
+        for num_threads in [1, 3, 4] {
+            let mut seen = Vec::new();
+            for thread in 1..=num_threads {
+                let (start, end) = slice_bounds(data.len(), thread, num_threads);
+                let slice = get_slice(&data, thread, num_threads);
+                assert_eq!(slice.len(), end - start);
+                seen.extend(slice.iter().map(|e| e.id));
+            }
+            seen.sort();
+            assert_eq!(seen, (1..=10).collect::<Vec<_>>());
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use surrealdb::engine::remote::ws::{Client, Ws};
+use surrealdb::opt::auth::Root;
+use surrealdb::Surreal;
+
+use crate::importer::{ImportResult, Importer};
+use crate::types::{ArxivEntry, ArxivEntry2};
+
+#[derive(Debug, Clone)]
+pub struct SurrealConfig {
+    pub host: String,
+    pub port: String,
+    pub user: String,
+    pub password: String,
+    pub ns: String,
+    pub db: String,
+}
+
+pub struct SurrealImporter {
+    db: Surreal<Client>,
+}
+
+#[async_trait::async_trait]
+impl Importer for SurrealImporter {
+    type Config = SurrealConfig;
+
+    async fn connect(config: &SurrealConfig) -> ImportResult<Self> {
+        let address = format!("{}:{}", config.host, config.port);
+        let db = Surreal::new::<Ws>(address).await?;
+
+        db.signin(Root {
+            username: &config.user,
+            password: &config.password,
+        })
+        .await?;
+
+        db.use_ns(&config.ns).use_db(&config.db).await?;
+
+        Ok(SurrealImporter { db })
+    }
+
+    async fn insert_batch(&self, target: &str, batch: &[ArxivEntry]) -> ImportResult<()> {
+        // Address each record by its arxiv id instead of letting SurrealDB
+        // assign a random one: the checkpoint ledger only flushes every
+        // `FLUSH_EVERY` commits, so a crash can leave a just-inserted chunk
+        // marked undone and have it re-inserted on resume. With a random id
+        // that re-insert becomes a duplicate row; with a deterministic id
+        // it overwrites the same record instead.
+        for entry in batch {
+            let id = entry.id.to_string();
+            let content: ArxivEntry2 = entry.clone().into();
+            self.db
+                .update::<Option<ArxivEntry2>>((target, id))
+                .content(content)
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+#![cfg(test)]
+
+// Shared fixtures for loader/import tests: a throwaway ArxivEntry as JSON,
+// and a helper to materialize a set of lines as a temp NDJSON file.
+
+use rand::Rng;
+use std::path::PathBuf;
+
+pub(crate) fn sample_entry_json(id: u32) -> String {
+    format!(
+        r#"{{"abstract": null, "authors": null, "authors_parsed": [], "categories": null, "comments": null, "doi": null, "id": {}, "journal_ref": null, "license": null, "report_no": null, "submitter": null, "title": null, "update_date": null, "versions": []}}"#,
+        id
+    )
+}
+
+pub(crate) fn write_temp_ndjson(lines: &[&str]) -> PathBuf {
+    let mut path = std::env::temp_dir();
+    path.push(format!(
+        "parallel-surreal-test-{}.ndjson",
+        rand::rng().random_range(0..u64::MAX)
+    ));
+    std::fs::write(&path, lines.join("\n")).expect("write temp ndjson file");
+    path
+}
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ArxivEntry {
+    // Since abstract is a reserved word in Rust, we use `abstract_text` instead
+    #[serde(rename = "abstract")]
+    pub abstract_text: Option<String>,
+    pub authors: Option<String>,
+    pub authors_parsed: Vec<Vec<String>>,
+    pub categories: Option<String>,
+    pub comments: Option<String>,
+    pub doi: Option<String>,
+    pub id: u32,
+    pub journal_ref: Option<String>,
+    pub license: Option<String>,
+    pub report_no: Option<String>,
+    pub submitter: Option<String>,
+    pub title: Option<String>,
+    pub update_date: Option<String>,
+    pub versions: Vec<Version>,
+}
+
+// ArxivEntry struct without the id field
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ArxivEntry2 {
+    // Since abstract is a reserved word in Rust, we use `abstract_text` instead
+    #[serde(rename = "abstract")]
+    pub abstract_text: Option<String>,
+    pub authors: Option<String>,
+    pub authors_parsed: Vec<Vec<String>>,
+    pub categories: Option<String>,
+    pub comments: Option<String>,
+    pub doi: Option<String>,
+    pub journal_ref: Option<String>,
+    pub license: Option<String>,
+    pub report_no: Option<String>,
+    pub submitter: Option<String>,
+    pub title: Option<String>,
+    pub update_date: Option<String>,
+    pub versions: Vec<Version>,
+}
+
+// Map the ArxivEntry struct to the ArxivEntry2 struct
+impl From<ArxivEntry> for ArxivEntry2 {
+    fn from(entry: ArxivEntry) -> Self {
+        ArxivEntry2 {
+            abstract_text: entry.abstract_text,
+            authors: entry.authors,
+            authors_parsed: entry.authors_parsed,
+            categories: entry.categories,
+            comments: entry.comments,
+            doi: entry.doi,
+            journal_ref: entry.journal_ref,
+            license: entry.license,
+            report_no: entry.report_no,
+            submitter: entry.submitter,
+            title: entry.title,
+            update_date: entry.update_date,
+            versions: entry.versions,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Version {
+    pub created: String,
+    pub version: String,
+}